@@ -23,7 +23,7 @@
 //!          /--a--(())
 //! ( START )
 //!         \--b---()--c--(())
-//!                /\   
+//!                /\
 //!               b_|
 //! ```
 //!
@@ -46,7 +46,7 @@
 //!
 //! Another example: /u(w|o)!/
 //!
-//! AST:  
+//! AST:
 //! ```txt
 //! Seq
 //!  |-- Char(u)
@@ -65,18 +65,18 @@
 //! ```
 //!
 //!
-//! AST nodes will become transitions in the FSM  
+//! AST nodes will become transitions in the FSM
 //! FSM nodes are the connections in the AST
 //!
 //! This architecture mostly seems to work out, with the only problem currently being allocating nodes
 //! this appears to be something not every kind of regex part does.
 //!
-//! A char will allocate the node for its transition.  
-//! A seq won't to that, because the contents of the seq allocate everything, the seq is just a wrapper.  
+//! A char will allocate the node for its transition.
+//! A seq won't to that, because the contents of the seq allocate everything, the seq is just a wrapper.
 //! Now the question is: is seq unique and should be special cased, or can something like it exist?
 //!
 //! Does choice allocate a node? No, it does not, it only branches. So allocating seems like something
-//! that some kinds do, but not all of them.  
+//! that some kinds do, but not all of them.
 //!
 //! So allocating is something that is not fundamental to the compilation, but handled by each node.
 
@@ -91,12 +91,14 @@ impl Compiler {
         match regex {
             Regex::Char(char) => self.allocating(node_before, |_, _| TransitionType::Char(*char)),
             Regex::Sequence(terms) => {
-                if let Some(first) = terms.first() {
-                    let trans_to_first = self.compile(first, 0);
-                } else {
-                    TransitionType::Always;
-                };
-                todo!()
+                // A sequence is just a wrapper: each term threads the node it ended on into the
+                // next one. An empty sequence matches the empty string and leaves the cursor where
+                // it was.
+                let mut current = node_before;
+                for term in terms {
+                    current = self.compile(term, current);
+                }
+                current
             }
             Regex::Primitive(primitive) => self.allocating(node_before, |_, _| {
                 TransitionType::Primitive(match primitive {
@@ -105,20 +107,62 @@ impl Compiler {
                 })
             }),
             Regex::Choice(a, b) => {
-                todo!()
+                // Both branches fan out from the same node and are joined again with epsilon
+                // (`Always`) edges so whatever follows only has to attach to a single node.
+                let end_a = self.compile(a, node_before);
+                let end_b = self.compile(b, node_before);
+                let join = self.reserve_node_slot();
+                self.transition(end_a, join, TransitionType::Always);
+                self.transition(end_b, join, TransitionType::Always);
+                join
+            }
+            Regex::Repetition(inner) => {
+                // `x*` — an epsilon edge into a split node from which the body loops back. The
+                // split node is both the loop head and the exit, so callers attach after it.
+                let split = self.allocating(node_before, |_, _| TransitionType::Always);
+                let body_end = self.compile(inner, split);
+                self.transition(body_end, split, TransitionType::Always);
+                split
             }
-            Regex::Repetition(_) => {
-                todo!()
+            Regex::Set(elems) => {
+                // A character set is an n-ary choice over its elements, joined like `Choice`.
+                let join = self.reserve_node_slot();
+                for elem in elems {
+                    let end = self.compile(elem, node_before);
+                    self.transition(end, join, TransitionType::Always);
+                }
+                join
             }
-            Regex::Set(_) => {
-                todo!()
+            Regex::Range(range) => {
+                self.allocating(node_before, |_, _| TransitionType::Range(range.clone()))
             }
-            Regex::Range(_) => {
-                todo!()
+            Regex::Anchor(kind) => self.allocating(node_before, |_, _| {
+                TransitionType::Assert(match kind {
+                    parse::AnchorKind::Start => AnchorKind::Start,
+                    parse::AnchorKind::End => AnchorKind::End,
+                    parse::AnchorKind::WordBoundary => AnchorKind::WordBoundary,
+                    parse::AnchorKind::NotWordBoundary => AnchorKind::NotWordBoundary,
+                })
+            }),
+            Regex::Capture { index, name, inner } => {
+                // A group surrounds its body with `Save` markers that record the input offset when
+                // traversed: slot `2*index` on entry, slot `2*index + 1` on exit.
+                if let Some(name) = name {
+                    self.names.push((name.clone(), *index));
+                }
+                self.reserve_slots(*index);
+                let open = self.allocating(node_before, |_, _| TransitionType::Save(index * 2));
+                let inner_end = self.compile(inner, open);
+                self.allocating(inner_end, |_, _| TransitionType::Save(index * 2 + 1))
             }
         }
     }
 
+    /// Grows the recorded save-slot count so that it covers the group with the given `index`.
+    fn reserve_slots(&mut self, index: usize) {
+        self.slots = self.slots.max(index * 2 + 2);
+    }
+
     fn allocating<F: FnOnce(&mut Node, NodeIndex) -> TransitionType>(
         &mut self,
         node_before: NodeIndex,
@@ -128,23 +172,57 @@ impl Compiler {
         let mut next_node = Node::default();
         let this_condition = f(&mut next_node, next_node_slot);
         // fill the placeholder with the node we just created, forget the placeholder
-        let _ = std::mem::replace(self.nodes.get_mut(next_node_slot).unwrap(), next_node);
+        let _ = core::mem::replace(self.nodes.get_mut(next_node_slot).unwrap(), next_node);
+        self.transition(node_before, next_node_slot, this_condition);
+
+        next_node_slot
+    }
+
+    /// Adds a transition with the given condition from `from` to an already existing `to` node.
+    fn transition(&mut self, from: NodeIndex, to: NodeIndex, condition: TransitionType) {
         self.nodes
-            .get_mut(node_before)
+            .get_mut(from)
             .unwrap()
             .transitions
             .push(Transition {
-                target_node: next_node_slot,
-                condition: this_condition,
+                target_node: to,
+                condition,
             });
-
-        next_node_slot
     }
 }
 
+use crate::input::{Chars, Input};
 use crate::parse;
 use crate::parse::Regex;
-use std::ops::Range;
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::ops::{Range, RangeInclusive};
+
+/// A bitset over node indices, sized once per match attempt. Matching clears and reuses it for
+/// every character consumed instead of allocating a fresh `Vec<bool>` per step.
+struct Bitset {
+    words: Vec<u64>,
+}
+
+impl Bitset {
+    fn new(len: usize) -> Self {
+        Bitset {
+            words: vec![0; len.div_ceil(64)],
+        }
+    }
+
+    fn clear(&mut self) {
+        self.words.fill(0);
+    }
+
+    fn get(&self, index: usize) -> bool {
+        self.words[index / 64] & (1 << (index % 64)) != 0
+    }
+
+    fn set(&mut self, index: usize) {
+        self.words[index / 64] |= 1 << (index % 64);
+    }
+}
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 struct Transition {
@@ -154,9 +232,13 @@ struct Transition {
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 enum TransitionType {
-    Range(Range<char>),
+    Range(RangeInclusive<char>),
     Primitive(Primitive),
     Char(char),
+    /// Epsilon edge that records the current input offset into the given save slot.
+    Save(usize),
+    /// Zero-width edge only traversable when the assertion holds at the current offset.
+    Assert(AnchorKind),
     Always,
 }
 
@@ -166,6 +248,35 @@ enum Primitive {
     Digit,
 }
 
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+enum AnchorKind {
+    Start,
+    End,
+    WordBoundary,
+    NotWordBoundary,
+}
+
+impl AnchorKind {
+    /// Whether the assertion holds given the characters immediately `before` and `after` the
+    /// current position. Both ends of the input are represented by `None`, so `Start`/`End` read
+    /// off the same two values as the word-boundary variants instead of needing a separate
+    /// "are we at offset 0 / input.len()" check — which also means the simulation never has to
+    /// slice the subject text to evaluate an assertion, only to hand it the neighbouring chars.
+    fn holds(self, before: Option<char>, after: Option<char>) -> bool {
+        match self {
+            AnchorKind::Start => before.is_none(),
+            AnchorKind::End => after.is_none(),
+            AnchorKind::WordBoundary => is_word(before) != is_word(after),
+            AnchorKind::NotWordBoundary => is_word(before) == is_word(after),
+        }
+    }
+}
+
+/// Whether `c` is a word character (`[A-Za-z0-9_]`); the absence of a character is never a word.
+fn is_word(c: Option<char>) -> bool {
+    matches!(c, Some(c) if c.is_ascii_alphanumeric() || c == '_')
+}
+
 #[derive(Debug, Clone, Default, PartialEq, Eq)]
 struct Node {
     end: bool,
@@ -173,27 +284,466 @@ struct Node {
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
-struct RegexFsm {
+pub struct RegexFsm {
     nodes: Vec<Node>,
+    /// Number of capture save slots (`2 * number_of_groups`, group 0 is the whole match).
+    slots: usize,
+    /// Map from group name to group index for named captures.
+    names: Vec<(String, usize)>,
+    /// A literal string every match is guaranteed to begin with, used to fast-skip non-matching
+    /// start offsets. Empty when no such prefix exists.
+    prefix: String,
+}
+
+/// The result of a successful match, holding the recorded save offsets and a view of the input so
+/// captured substrings can be sliced back out.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Captures<'a> {
+    input: &'a str,
+    slots: Vec<Option<usize>>,
+    names: Vec<(String, usize)>,
+}
+
+impl<'a> Captures<'a> {
+    /// Returns the substring captured by group `index`, or `None` if the group did not participate
+    /// in the match. Group 0 is the whole match.
+    pub fn get(&self, index: usize) -> Option<&'a str> {
+        let start = (*self.slots.get(index * 2)?)?;
+        let end = (*self.slots.get(index * 2 + 1)?)?;
+        Some(&self.input[start..end])
+    }
+
+    /// Returns the substring captured by the named group `name`, if any.
+    pub fn name(&self, name: &str) -> Option<&'a str> {
+        let index = self.names.iter().find(|(n, _)| n == name).map(|(_, i)| *i)?;
+        self.get(index)
+    }
+}
+
+/// The position-dependent context an `Assert` edge is evaluated against, and the save-slot offset
+/// recorded when a `Save` edge is crossed: `pos`, plus the characters immediately `before` and
+/// `after` it (both `None` at the respective end of the input).
+#[derive(Debug, Clone, Copy)]
+struct Cursor {
+    pos: usize,
+    before: Option<char>,
+    after: Option<char>,
+}
+
+impl RegexFsm {
+    /// Returns whether the whole `input` is matched by the expression.
+    ///
+    /// This is a breadth-first NFA simulation (Thompson/Pike style): instead of backtracking we
+    /// keep the full set of states the machine could be in, so matching stays linear in the input
+    /// length even for patterns like `a*` with nested repetitions.
+    pub fn is_match(&self, input: &str) -> bool {
+        self.is_match_input(Chars::new(input))
+    }
+
+    /// Same as [`is_match`](Self::is_match), but reads from any [`Input`] source instead of a
+    /// contiguous `&str` — raw bytes, or non-contiguous streamed chunks, without first
+    /// concatenating them into one buffer.
+    pub fn is_match_input<I: Input>(&self, mut input: I) -> bool {
+        let mut noop = |state: (), _slot: usize, _pos: usize| state;
+
+        let mut current = Vec::new();
+        let mut visited = Bitset::new(self.nodes.len());
+        let cursor = Cursor {
+            pos: 0,
+            before: None,
+            after: input.peek(),
+        };
+        self.epsilon_closure(0, &mut current, &mut visited, cursor, (), &mut noop);
+
+        while let Some(c) = input.next() {
+            let cursor = Cursor {
+                pos: input.offset(),
+                before: Some(c),
+                after: input.peek(),
+            };
+            let mut next = Vec::new();
+            visited.clear();
+            self.advance(&current, c, &mut next, &mut visited, cursor, &mut noop);
+            if next.is_empty() {
+                return false;
+            }
+            current = next;
+        }
+
+        current.iter().any(|&(node, ())| self.nodes[node].end)
+    }
+
+    /// Searches for the first (leftmost-first) match anywhere in `input` and returns its byte
+    /// range, or `None` if the expression never matches. Uses the same leftmost-first thread
+    /// priority as [`captures`](Self::captures), so `find(s)` and `captures(s).unwrap().get(0)`
+    /// always agree on the overall match span.
+    ///
+    /// When the pattern has a known literal prefix, only offsets that begin with the prefix's first
+    /// byte are tried, skipping the bulk of the input directly with a byte scan; otherwise every
+    /// position is attempted in turn.
+    pub fn find(&self, input: &str) -> Option<Range<usize>> {
+        let first = match self.prefix.as_bytes().first() {
+            Some(&b) => b,
+            None => return self.find_scanning(input),
+        };
+
+        let mut start = 0;
+        while let Some(off) = input.as_bytes()[start..].iter().position(|&b| b == first) {
+            let candidate = start + off;
+            if input.is_char_boundary(candidate) {
+                if let Some(end) = self.match_at(input, candidate) {
+                    return Some(candidate..end);
+                }
+            }
+            start = candidate + 1;
+        }
+        None
+    }
+
+    /// The position-by-position fallback search used when no literal prefix is available.
+    fn find_scanning(&self, input: &str) -> Option<Range<usize>> {
+        let mut starts: Vec<usize> = input.char_indices().map(|(i, _)| i).collect();
+        starts.push(input.len());
+
+        for start in starts {
+            if let Some(end) = self.match_at(input, start) {
+                return Some(start..end);
+            }
+        }
+        None
+    }
+
+    /// Runs the simulation starting at byte offset `start` with leftmost-first (Pike VM) thread
+    /// priority and returns the byte offset the highest-priority accepting thread ends on, if any.
+    fn match_at(&self, input: &str, start: usize) -> Option<usize> {
+        let mut noop = |state: (), _slot: usize, _pos: usize| state;
+
+        let mut clist: Vec<(NodeIndex, ())> = Vec::new();
+        let mut visited = Bitset::new(self.nodes.len());
+        let cursor = Cursor {
+            pos: start,
+            before: input[..start].chars().next_back(),
+            after: input[start..].chars().next(),
+        };
+        self.epsilon_closure(0, &mut clist, &mut visited, cursor, (), &mut noop);
+
+        let mut matched = None;
+        let mut pos = start;
+        let mut chars = input[start..].char_indices().map(|(i, c)| (start + i, c));
+        let mut c = chars.next();
+
+        loop {
+            let mut nlist = Vec::new();
+            visited.clear();
+            let consume = c.map(|(offset, ch)| {
+                let next_pos = offset + ch.len_utf8();
+                let cursor = Cursor {
+                    pos: next_pos,
+                    before: Some(ch),
+                    after: input[next_pos..].chars().next(),
+                };
+                (ch, next_pos, cursor)
+            });
+
+            for &(node, ()) in &clist {
+                if self.nodes[node].end {
+                    // higher-priority threads already ran; cut off the lower-priority ones
+                    matched = Some(pos);
+                    break;
+                }
+                if let Some((ch, _, cursor)) = consume {
+                    for transition in &self.nodes[node].transitions {
+                        if transition.condition.matches(ch) {
+                            self.epsilon_closure(
+                                transition.target_node,
+                                &mut nlist,
+                                &mut visited,
+                                cursor,
+                                (),
+                                &mut noop,
+                            );
+                        }
+                    }
+                }
+            }
+
+            match consume {
+                Some((_, next_pos, _)) => {
+                    pos = next_pos;
+                    clist = nlist;
+                    c = chars.next();
+                }
+                None => break,
+            }
+        }
+
+        matched
+    }
+
+    /// Searches `input` for the first (leftmost-first) match and returns its [`Captures`].
+    pub fn captures<'a>(&self, input: &'a str) -> Option<Captures<'a>> {
+        let mut starts: Vec<usize> = input.char_indices().map(|(i, _)| i).collect();
+        starts.push(input.len());
+
+        for start in starts {
+            if let Some(slots) = self.exec(input, start) {
+                return Some(Captures {
+                    input,
+                    slots,
+                    names: self.names.clone(),
+                });
+            }
+        }
+        None
+    }
+
+    /// Pike VM: a breadth-first simulation that threads a `Vec` of save offsets through every
+    /// active state. Threads are kept in leftmost-first priority order so the captures of the
+    /// highest-priority accepting thread win, giving deterministic, greedy results. Shares its
+    /// epsilon-closure walk with `match_at` through `epsilon_closure`, differing only in the
+    /// per-thread state it carries and what crossing a `Save` edge does with it.
+    fn exec(&self, input: &str, start: usize) -> Option<Vec<Option<usize>>> {
+        let mut record_save = |mut saves: Vec<Option<usize>>, slot: usize, pos: usize| {
+            if let Some(entry) = saves.get_mut(slot) {
+                *entry = Some(pos);
+            }
+            saves
+        };
+
+        let mut clist: Vec<(NodeIndex, Vec<Option<usize>>)> = Vec::new();
+        let mut visited = Bitset::new(self.nodes.len());
+        let cursor = Cursor {
+            pos: start,
+            before: input[..start].chars().next_back(),
+            after: input[start..].chars().next(),
+        };
+        self.epsilon_closure(
+            0,
+            &mut clist,
+            &mut visited,
+            cursor,
+            vec![None; self.slots],
+            &mut record_save,
+        );
+
+        let mut matched = None;
+        let mut chars = input[start..].char_indices().map(|(i, c)| (start + i, c));
+        let mut c = chars.next();
+
+        loop {
+            let mut nlist = Vec::new();
+            visited.clear();
+            let consume = c.map(|(offset, ch)| {
+                let next_pos = offset + ch.len_utf8();
+                let cursor = Cursor {
+                    pos: next_pos,
+                    before: Some(ch),
+                    after: input[next_pos..].chars().next(),
+                };
+                (ch, cursor)
+            });
+
+            for (node, saves) in &clist {
+                if self.nodes[*node].end {
+                    // higher-priority threads already ran; cut off the lower-priority ones
+                    matched = Some(saves.clone());
+                    break;
+                }
+                if let Some((ch, cursor)) = consume {
+                    for transition in &self.nodes[*node].transitions {
+                        if transition.condition.matches(ch) {
+                            self.epsilon_closure(
+                                transition.target_node,
+                                &mut nlist,
+                                &mut visited,
+                                cursor,
+                                saves.clone(),
+                                &mut record_save,
+                            );
+                        }
+                    }
+                }
+            }
+
+            match c {
+                Some(_) => {
+                    clist = nlist;
+                    c = chars.next();
+                }
+                None => break,
+            }
+        }
+
+        matched
+    }
+
+    /// Builds the next state set by consuming `c` from every `(node, state)` pair in `current`,
+    /// following each matching transition into the epsilon-closure of its target.
+    fn advance<T: Clone>(
+        &self,
+        current: &[(NodeIndex, T)],
+        c: char,
+        next: &mut Vec<(NodeIndex, T)>,
+        visited: &mut Bitset,
+        cursor: Cursor,
+        on_save: &mut impl FnMut(T, usize, usize) -> T,
+    ) {
+        for (node, state) in current {
+            for transition in &self.nodes[*node].transitions {
+                if transition.condition.matches(c) {
+                    self.epsilon_closure(
+                        transition.target_node,
+                        next,
+                        visited,
+                        cursor,
+                        state.clone(),
+                        on_save,
+                    );
+                }
+            }
+        }
+    }
+
+    /// Adds `node` and everything reachable from it through epsilon edges to `list`, threading an
+    /// arbitrary per-thread state `T` that `on_save(state, slot, pos)` folds in whenever a `Save`
+    /// edge is crossed. `cursor` carries the position and its surrounding characters so `Assert`
+    /// edges can be evaluated, and `visited` bounds the recursion so repetition cycles such as
+    /// `a*` terminate instead of looping forever.
+    ///
+    /// This single traversal backs both the plain reachability simulation (`is_match`, `match_at`,
+    /// with `T = ()`) and the capturing Pike VM (`exec`, with `T = Vec<Option<usize>>`) so the two
+    /// can't drift apart on what counts as epsilon-reachable.
+    fn epsilon_closure<T: Clone>(
+        &self,
+        node: NodeIndex,
+        list: &mut Vec<(NodeIndex, T)>,
+        visited: &mut Bitset,
+        cursor: Cursor,
+        state: T,
+        on_save: &mut impl FnMut(T, usize, usize) -> T,
+    ) {
+        if visited.get(node) {
+            return;
+        }
+        visited.set(node);
+        list.push((node, state.clone()));
+        for transition in &self.nodes[node].transitions {
+            match transition.condition {
+                TransitionType::Always => {
+                    self.epsilon_closure(
+                        transition.target_node,
+                        list,
+                        visited,
+                        cursor,
+                        state.clone(),
+                        on_save,
+                    );
+                }
+                TransitionType::Assert(kind) if kind.holds(cursor.before, cursor.after) => {
+                    self.epsilon_closure(
+                        transition.target_node,
+                        list,
+                        visited,
+                        cursor,
+                        state.clone(),
+                        on_save,
+                    );
+                }
+                TransitionType::Save(slot) => {
+                    let next_state = on_save(state.clone(), slot, cursor.pos);
+                    self.epsilon_closure(
+                        transition.target_node,
+                        list,
+                        visited,
+                        cursor,
+                        next_state,
+                        on_save,
+                    );
+                }
+                _ => {}
+            }
+        }
+    }
+}
+
+impl TransitionType {
+    /// Whether this transition's condition is satisfied by the character `c`. `Always` edges are
+    /// epsilon transitions and never consume input, so they never match here.
+    fn matches(&self, c: char) -> bool {
+        match self {
+            TransitionType::Char(expected) => *expected == c,
+            TransitionType::Range(range) => range.contains(&c),
+            TransitionType::Primitive(Primitive::Word) => {
+                c.is_ascii_alphanumeric() || c == '_'
+            }
+            TransitionType::Primitive(Primitive::Digit) => c.is_ascii_digit(),
+            // epsilon edges never consume input
+            TransitionType::Save(_) | TransitionType::Assert(_) | TransitionType::Always => false,
+        }
+    }
 }
 
 #[derive(Debug, Default)]
 struct Compiler {
     nodes: Vec<Node>,
+    /// Highest save-slot count seen so far (group 0 reserves the first two slots, see `compile`).
+    slots: usize,
+    names: Vec<(String, usize)>,
 }
 
 /// Compiles the parsed Regex into a FSM
-fn compile(regex: &Regex) -> RegexFsm {
+pub fn compile(regex: &Regex) -> RegexFsm {
     let mut compiler = Compiler::default();
 
     // reserve the start node
     compiler.reserve_node_slot();
 
-    compiler.compile(regex, 0);
+    // wrap the whole expression in group 0 so the matcher records the overall match bounds
+    compiler.reserve_slots(0);
+    let start = compiler.allocating(0, |_, _| TransitionType::Save(0));
+    let body_end = compiler.compile(regex, start);
+    let end = compiler.allocating(body_end, |_, _| TransitionType::Save(1));
+    // the node the whole expression ends on is the only accepting state
+    compiler.nodes.get_mut(end).unwrap().end = true;
+
+    let prefix = extract_prefix(&compiler.nodes);
 
     RegexFsm {
         nodes: compiler.nodes,
+        slots: compiler.slots,
+        names: compiler.names,
+        prefix,
+    }
+}
+
+/// Extracts the literal prefix every match must begin with by following the unique chain of `Char`
+/// edges from the start node, transparently skipping epsilon (`Always`/`Save`) edges. The walk
+/// stops as soon as the path branches, revisits a node (a repetition cycle), or hits a non-literal
+/// condition such as a range, primitive or assertion.
+fn extract_prefix(nodes: &[Node]) -> String {
+    let mut prefix = String::new();
+    let mut node = 0;
+    let mut visited = vec![false; nodes.len()];
+
+    loop {
+        if visited[node] {
+            break;
+        }
+        visited[node] = true;
+
+        let transitions = &nodes[node].transitions;
+        if transitions.len() != 1 {
+            break;
+        }
+        match &transitions[0].condition {
+            TransitionType::Char(c) => prefix.push(*c),
+            TransitionType::Always | TransitionType::Save(_) => {}
+            _ => break,
+        }
+        node = transitions[0].target_node;
     }
+
+    prefix
 }
 
 impl Compiler {
@@ -207,14 +757,21 @@ impl Compiler {
 #[cfg(test)]
 mod test {
     use crate::compile::{Node, RegexFsm, Transition, TransitionType};
-    use crate::parse::Regex;
+    use crate::parse::{Parser, Regex};
+    use alloc::string::ToString;
+
+    fn fsm(regex: &str) -> RegexFsm {
+        super::compile(&Parser::parse(regex).unwrap())
+    }
 
     ///
-    /// regex: /ðŸŒˆ/
-    /// fsm:  () --ðŸŒˆ-- (())
+    /// regex: /🌈/
+    /// fsm:  () --S0-- () --🌈-- () --S1-- (())
+    ///
+    /// The char transition is wrapped in the group-0 `Save` markers that bound the whole match.
     #[test]
     fn single_char() {
-        let ast = Regex::Char('ðŸŒˆ');
+        let ast = Regex::Char('🌈');
         let fsm = super::compile(&ast);
         assert_eq!(
             fsm,
@@ -224,15 +781,131 @@ mod test {
                         end: false,
                         transitions: vec![Transition {
                             target_node: 1,
-                            condition: TransitionType::Char('ðŸŒˆ')
+                            condition: TransitionType::Save(0)
+                        }]
+                    },
+                    Node {
+                        end: false,
+                        transitions: vec![Transition {
+                            target_node: 2,
+                            condition: TransitionType::Char('🌈')
+                        }]
+                    },
+                    Node {
+                        end: false,
+                        transitions: vec![Transition {
+                            target_node: 3,
+                            condition: TransitionType::Save(1)
                         }]
                     },
                     Node {
                         end: true,
                         transitions: vec![]
                     }
-                ]
+                ],
+                slots: 2,
+                names: vec![],
+                prefix: "🌈".to_string(),
             }
         )
     }
+
+    #[test]
+    fn match_choice() {
+        let fsm = fsm("a|b");
+        assert!(fsm.is_match("a"));
+        assert!(fsm.is_match("b"));
+        assert!(!fsm.is_match("c"));
+    }
+
+    #[test]
+    fn match_repetition() {
+        let fsm = fsm("ab*c");
+        assert!(fsm.is_match("ac"));
+        assert!(fsm.is_match("abc"));
+        assert!(fsm.is_match("abbbbc"));
+        assert!(!fsm.is_match("ab"));
+    }
+
+    #[test]
+    fn match_set_and_primitives() {
+        assert!(fsm("[a-z]").is_match("q"));
+        assert!(!fsm("[a-z]").is_match("Q"));
+        assert!(fsm("\\w\\d").is_match("a1"));
+        assert!(!fsm("\\w\\d").is_match("aa"));
+    }
+
+    #[test]
+    fn range_matches_inclusive_bounds() {
+        // a range is inclusive on both ends: `[a-z]` must match both `a` and `z`, not just the
+        // characters strictly between them.
+        assert!(fsm("[a-z]").is_match("a"));
+        assert!(fsm("[a-z]").is_match("z"));
+        assert!(fsm("[0-9]").is_match("9"));
+    }
+
+    #[test]
+    fn find_range() {
+        let fsm = fsm("b*c");
+        assert_eq!(fsm.find("aabbbcd"), Some(2..6));
+        assert_eq!(fsm.find("xyz"), None);
+    }
+
+    #[test]
+    fn literal_prefix() {
+        assert_eq!(fsm("abc").prefix, "abc");
+        // a leading repetition branches immediately, leaving no guaranteed prefix
+        assert_eq!(fsm("a*bc").prefix, "");
+        // the prefix fast-path still finds the leftmost match
+        assert_eq!(fsm("abc").find("xxabcyy"), Some(2..5));
+    }
+
+    #[test]
+    fn captures_indexed() {
+        let caps = fsm("a(b*)c").captures("abbbc").unwrap();
+        assert_eq!(caps.get(0), Some("abbbc"));
+        assert_eq!(caps.get(1), Some("bbb"));
+        assert_eq!(caps.get(2), None);
+    }
+
+    #[test]
+    fn find_and_captures_agree_on_ambiguous_alternation() {
+        // leftmost-first: the earlier alternative wins even though the later one would match more.
+        let fsm = fsm("a|ab");
+        assert_eq!(fsm.find("ab"), Some(0..1));
+        assert_eq!(fsm.captures("ab").unwrap().get(0), Some("a"));
+    }
+
+    #[test]
+    fn anchors_match() {
+        assert_eq!(fsm("^ab").find("abx"), Some(0..2));
+        assert_eq!(fsm("^ab").find("xabab"), None);
+        assert_eq!(fsm("a$").find("ba"), Some(1..2));
+        assert_eq!(fsm("a$").find("ab"), None);
+    }
+
+    #[test]
+    fn word_boundaries() {
+        assert_eq!(fsm("\\bword\\b").find("a word here"), Some(2..6));
+        assert_eq!(fsm("\\Bo").find("foo"), Some(1..2));
+        assert!(fsm("\\bo").find("foo").is_none());
+    }
+
+    #[test]
+    fn captures_named() {
+        let caps = fsm("(?<mid>b*)").captures("bb").unwrap();
+        assert_eq!(caps.name("mid"), Some("bb"));
+        assert_eq!(caps.name("nope"), None);
+    }
+
+    #[test]
+    fn is_match_input_over_non_str_sources() {
+        use crate::input::{Bytes, Chunks};
+
+        let fsm = fsm("ab*c");
+        assert!(fsm.is_match_input(Bytes::new(b"abbbc")));
+        assert!(!fsm.is_match_input(Bytes::new(b"ab")));
+        // the same pattern matches across a chunk boundary without concatenating the chunks first
+        assert!(fsm.is_match_input(Chunks::new(&["ab", "", "bc"])));
+    }
 }
@@ -0,0 +1,169 @@
+//! The input abstraction the parser reads from.
+//!
+//! Instead of hard-coding `Peekable<Chars>`, the parser is generic over [`Input`], so it can run
+//! over a borrowed `&str`, raw ASCII bytes, or a sequence of non-contiguous chunks (e.g. streamed
+//! data) without first concatenating the buffers. Offsets are counted in characters consumed, which
+//! is what the parser uses for error positions.
+
+use core::iter::Peekable;
+use core::str::Chars as StrChars;
+
+/// A character source the parser reads from.
+pub trait Input {
+    /// Returns the next character without consuming it.
+    fn peek(&mut self) -> Option<char>;
+    /// Consumes and returns the next character.
+    fn next(&mut self) -> Option<char>;
+    /// The number of characters consumed so far, i.e. the offset of the next character.
+    fn offset(&self) -> usize;
+}
+
+/// [`Input`] over a UTF-8 `&str` — the default convenience source.
+#[derive(Debug)]
+pub struct Chars<'a> {
+    chars: Peekable<StrChars<'a>>,
+    offset: usize,
+}
+
+impl<'a> Chars<'a> {
+    pub fn new(s: &'a str) -> Self {
+        Self {
+            chars: s.chars().peekable(),
+            offset: 0,
+        }
+    }
+}
+
+impl Input for Chars<'_> {
+    fn peek(&mut self) -> Option<char> {
+        self.chars.peek().copied()
+    }
+
+    fn next(&mut self) -> Option<char> {
+        let c = self.chars.next();
+        if c.is_some() {
+            self.offset += 1;
+        }
+        c
+    }
+
+    fn offset(&self) -> usize {
+        self.offset
+    }
+}
+
+/// [`Input`] over raw ASCII bytes, each byte read as one character.
+#[derive(Debug)]
+pub struct Bytes<'a> {
+    bytes: &'a [u8],
+    offset: usize,
+}
+
+impl<'a> Bytes<'a> {
+    pub fn new(bytes: &'a [u8]) -> Self {
+        Self { bytes, offset: 0 }
+    }
+}
+
+impl Input for Bytes<'_> {
+    fn peek(&mut self) -> Option<char> {
+        self.bytes.get(self.offset).map(|&b| b as char)
+    }
+
+    fn next(&mut self) -> Option<char> {
+        let c = self.bytes.get(self.offset).map(|&b| b as char);
+        if c.is_some() {
+            self.offset += 1;
+        }
+        c
+    }
+
+    fn offset(&self) -> usize {
+        self.offset
+    }
+}
+
+/// [`Input`] over a sequence of string chunks, presented as one continuous character stream without
+/// concatenating the underlying buffers. Empty chunks are skipped transparently.
+#[derive(Debug)]
+pub struct Chunks<'a> {
+    chunks: &'a [&'a str],
+    chunk: usize,
+    inner: Option<Peekable<StrChars<'a>>>,
+    offset: usize,
+}
+
+impl<'a> Chunks<'a> {
+    pub fn new(chunks: &'a [&'a str]) -> Self {
+        let mut this = Self {
+            chunks,
+            chunk: 0,
+            inner: None,
+            offset: 0,
+        };
+        this.load_chunk();
+        this
+    }
+
+    /// Points `inner` at the first non-empty chunk at or after `chunk`, or clears it at the end.
+    fn load_chunk(&mut self) {
+        while self.chunk < self.chunks.len() {
+            let mut iter = self.chunks[self.chunk].chars().peekable();
+            if iter.peek().is_some() {
+                self.inner = Some(iter);
+                return;
+            }
+            self.chunk += 1;
+        }
+        self.inner = None;
+    }
+}
+
+impl Input for Chunks<'_> {
+    fn peek(&mut self) -> Option<char> {
+        loop {
+            if let Some(c) = self.inner.as_mut().and_then(|iter| iter.peek().copied()) {
+                return Some(c);
+            }
+            // the current chunk is exhausted: move on to the next non-empty one, if any
+            self.inner.as_ref()?;
+            self.chunk += 1;
+            self.load_chunk();
+        }
+    }
+
+    fn next(&mut self) -> Option<char> {
+        loop {
+            if let Some(c) = self.inner.as_mut().and_then(|iter| iter.next()) {
+                self.offset += 1;
+                return Some(c);
+            }
+            if self.chunk >= self.chunks.len() {
+                return None;
+            }
+            self.chunk += 1;
+            self.load_chunk();
+            self.inner.as_ref()?;
+        }
+    }
+
+    fn offset(&self) -> usize {
+        self.offset
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{Chunks, Input};
+
+    #[test]
+    fn chunked_stream_reads_across_buffers() {
+        let mut input = Chunks::new(&["ab", "", "c"]);
+        let mut seen = alloc::string::String::new();
+        while let Some(c) = input.next() {
+            seen.push(c);
+        }
+        assert_eq!(seen, "abc");
+        assert_eq!(input.offset(), 3);
+    }
+}
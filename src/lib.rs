@@ -0,0 +1,14 @@
+//! A small regular expression engine: recursive-descent [`parse`]r, [`compile`]r to a finite state
+//! machine, and a linear-time NFA matcher.
+//!
+//! The crate is `no_std` by default and only relies on `alloc`. The `std` feature is purely a
+//! convenience toggle for downstream crates that want to opt back into the standard library.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+#[macro_use]
+extern crate alloc;
+
+pub mod compile;
+pub mod input;
+pub mod parse;
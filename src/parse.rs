@@ -15,7 +15,11 @@
 //! <base> ::= <char>
 //!         | '\' <char>
 //!         | '(' <regex> ')'
+//!         | '(' '?' ':' <regex> ')'
+//!         | '(' '?' '<' <name> '>' <regex> ')'
 //!         | '[' { <set-elem> } ']'
+//!         | '^'
+//!         | '$'
 //!
 //! <set-elem> ::= <char>
 //!             | <range>
@@ -23,18 +27,29 @@
 //! <range> ::= <char> '-' <char>
 //! ```
 
-use std::iter::Peekable;
-use std::ops::Range;
-use std::str::Chars;
+use crate::input::{Chars, Input};
+use alloc::boxed::Box;
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::ops::RangeInclusive;
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum Regex {
     Choice(Box<Regex>, Box<Regex>),
     Sequence(Vec<Regex>),
     Repetition(Box<Regex>),
+    /// A capturing group. `index` is assigned left-to-right by opening parenthesis (group 0 is
+    /// the whole match), `name` is set for `(?<name>...)` groups.
+    Capture {
+        index: usize,
+        name: Option<String>,
+        inner: Box<Regex>,
+    },
     Set(Vec<Regex>),
-    Range(Range<char>),
+    Range(RangeInclusive<char>),
     Primitive(Primitive),
+    /// A zero-width assertion such as `^`, `$` or `\b` that matches a position rather than input.
+    Anchor(AnchorKind),
     Char(char),
 }
 
@@ -44,37 +59,158 @@ pub enum Primitive {
     Digit,
 }
 
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum AnchorKind {
+    /// `^`, the start of the input.
+    Start,
+    /// `$`, the end of the input.
+    End,
+    /// `\b`, a boundary between a word and a non-word character (or an edge).
+    WordBoundary,
+    /// `\B`, a position that is not a word boundary.
+    NotWordBoundary,
+}
+
+/// A parse error carrying the char offset at which it occurred, a machine-readable [`ParseErrorKind`]
+/// and a human-readable message.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    pub pos: usize,
+    pub kind: ParseErrorKind,
+    pub message: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseErrorKind {
+    /// The input ended while more was expected.
+    UnexpectedEof,
+    /// A `)` had no matching `(`, or a group was never closed.
+    UnbalancedParen,
+    /// A `[` character set was never closed with `]`.
+    UnclosedSet,
+    /// An unknown escape sequence `\<c>`.
+    InvalidEscape(char),
+    /// A character range whose end precedes its start, e.g. `[z-a]`.
+    EmptyRange,
+    /// A malformed `(?...)` group marker.
+    InvalidGroup,
+}
+
 #[derive(Debug)]
-pub struct Parser<'a> {
-    chars: Peekable<Chars<'a>>,
+pub struct Parser<I: Input> {
+    input: I,
+    /// The index to assign to the next capturing group. Group 0 is reserved for the whole match.
+    next_group: usize,
+    /// When set, recoverable errors are collected into `errors` instead of aborting the parse.
+    recover: bool,
+    errors: Vec<ParseError>,
 }
 
-type RegexResult = Result<Regex, ()>;
+type RegexResult = Result<Regex, ParseError>;
+
+impl<'a> Parser<Chars<'a>> {
+    pub fn parse(regex: &'a str) -> Result<Regex, ParseError> {
+        Self::parse_input(Chars::new(regex))
+    }
 
-impl<'a> Parser<'a> {
-    pub fn parse(regex: &'a str) -> Result<Regex, ()> {
-        let chars = regex.chars();
-        let mut parser = Self {
-            chars: chars.peekable(),
+    /// Parses in recovery mode: recoverable errors (a stray `)`, an unclosed group, ...) are
+    /// recorded and parsing continues, so a single pass can report several problems. Returns a
+    /// best-effort AST alongside every error encountered.
+    pub fn parse_recovering(regex: &'a str) -> (Regex, Vec<ParseError>) {
+        Self::parse_input_recovering(Chars::new(regex))
+    }
+}
+
+impl<I: Input> Parser<I> {
+    /// Parses the regex read from an arbitrary [`Input`] source.
+    pub fn parse_input(input: I) -> Result<Regex, ParseError> {
+        let mut parser = Self::new(input, false);
+        let ast = parser.regex()?;
+        if let Some(c) = parser.peek() {
+            return Err(parser.error(
+                ParseErrorKind::UnbalancedParen,
+                format!("unexpected `{c}`"),
+            ));
+        }
+        Ok(ast)
+    }
+
+    /// Recovery-mode counterpart of [`parse_input`](Self::parse_input).
+    pub fn parse_input_recovering(input: I) -> (Regex, Vec<ParseError>) {
+        let mut parser = Self::new(input, true);
+        let mut parts = Vec::new();
+
+        while parser.peek().is_some() {
+            match parser.regex() {
+                Ok(part) => parts.push(part),
+                Err(err) => parser.errors.push(err),
+            }
+            // `regex` only stops early on a stray `)`; record it and skip past so we make progress.
+            if let Some(c @ ')') = parser.peek() {
+                let err = parser.error(ParseErrorKind::UnbalancedParen, format!("unmatched `{c}`"));
+                parser.errors.push(err);
+                let _ = parser.next();
+            }
+        }
+
+        let ast = if parts.len() == 1 {
+            parts.pop().unwrap()
+        } else {
+            Regex::Sequence(parts)
         };
-        parser.regex()
+        (ast, parser.errors)
+    }
+
+    fn new(input: I, recover: bool) -> Self {
+        Self {
+            input,
+            next_group: 1,
+            recover,
+            errors: Vec::new(),
+        }
+    }
+
+    /// Builds a [`ParseError`] at the current offset.
+    fn error(&self, kind: ParseErrorKind, message: impl Into<String>) -> ParseError {
+        ParseError {
+            pos: self.input.offset(),
+            kind,
+            message: message.into(),
+        }
+    }
+
+    /// The canonical "ran out of input" error at the current offset.
+    fn eof(&self) -> ParseError {
+        self.error(ParseErrorKind::UnexpectedEof, "unexpected end of input")
     }
 
     #[must_use]
     fn next(&mut self) -> Option<char> {
-        self.chars.next()
+        self.input.next()
     }
 
-    fn expect(&mut self, c: char) {
+    /// Consumes `c` if present. A mismatch is an `UnbalancedParen`; in recovery mode it is recorded
+    /// and treated as if `c` had been there so parsing can continue.
+    fn expect(&mut self, c: char) -> Result<(), ParseError> {
         if self.peek() == Some(c) {
             let _ = self.next();
+            Ok(())
         } else {
-            panic!("handle this better")
+            let err = self.error(
+                ParseErrorKind::UnbalancedParen,
+                format!("expected `{c}`"),
+            );
+            if self.recover {
+                self.errors.push(err);
+                Ok(())
+            } else {
+                Err(err)
+            }
         }
     }
 
     fn peek(&mut self) -> Option<char> {
-        self.chars.peek().cloned()
+        self.input.peek()
     }
 
     // regex term types
@@ -120,24 +256,72 @@ impl<'a> Parser<'a> {
         match self.peek() {
             Some('(') => {
                 let _ = self.next();
-                let regex = self.regex()?;
-                self.expect(')');
-                Ok(regex)
+                if let Some('?') = self.peek() {
+                    let _ = self.next();
+                    match self.next() {
+                        // non-capturing group `(?:...)`
+                        Some(':') => {
+                            let regex = self.regex()?;
+                            self.expect(')')?;
+                            Ok(regex)
+                        }
+                        // named capturing group `(?<name>...)`
+                        Some('<') => {
+                            let mut name = String::new();
+                            loop {
+                                match self.next() {
+                                    Some('>') => break,
+                                    Some(c) => name.push(c),
+                                    None => return Err(self.eof()),
+                                }
+                            }
+                            self.capture(Some(name))
+                        }
+                        _ => Err(self.error(
+                            ParseErrorKind::InvalidGroup,
+                            "expected `:` or `<name>` after `(?`",
+                        )),
+                    }
+                } else {
+                    self.capture(None)
+                }
+            }
+            Some('^') => {
+                let _ = self.next();
+                Ok(Regex::Anchor(AnchorKind::Start))
+            }
+            Some('$') => {
+                let _ = self.next();
+                Ok(Regex::Anchor(AnchorKind::End))
             }
             Some('\\') => {
                 let _ = self.next();
-                let esc = self.next().ok_or(())?;
-                Ok(Regex::Primitive(match esc {
-                    'w' => Primitive::Word,
-                    'd' => Primitive::Digit,
-                    _ => return Err(()),
-                }))
+                let esc = self.next().ok_or_else(|| self.eof())?;
+                Ok(match esc {
+                    'w' => Regex::Primitive(Primitive::Word),
+                    'd' => Regex::Primitive(Primitive::Digit),
+                    'b' => Regex::Anchor(AnchorKind::WordBoundary),
+                    'B' => Regex::Anchor(AnchorKind::NotWordBoundary),
+                    _ => {
+                        return Err(self.error(
+                            ParseErrorKind::InvalidEscape(esc),
+                            format!("unknown escape `\\{esc}`"),
+                        ))
+                    }
+                })
             }
             Some('[') => {
                 let _ = self.next();
                 let mut elems = Vec::new();
-                while self.peek() != Some(']') {
-                    elems.push(self.set_elem()?);
+                loop {
+                    match self.peek() {
+                        Some(']') => break,
+                        None => return Err(self.error(
+                            ParseErrorKind::UnclosedSet,
+                            "missing `]` to close character set",
+                        )),
+                        _ => elems.push(self.set_elem()?),
+                    }
                 }
                 let _ = self.next();
                 Ok(Regex::Set(elems))
@@ -146,17 +330,37 @@ impl<'a> Parser<'a> {
                 let _ = self.next();
                 Ok(Regex::Char(char))
             }
-            None => Err(()),
+            None => Err(self.eof()),
         }
     }
 
+    /// Parses the body of a capturing group after the opening marker has been consumed, assigning
+    /// it the next group index. The closing `)` is expected afterwards.
+    fn capture(&mut self, name: Option<String>) -> RegexResult {
+        let index = self.next_group;
+        self.next_group += 1;
+        let inner = self.regex()?;
+        self.expect(')')?;
+        Ok(Regex::Capture {
+            index,
+            name,
+            inner: Box::new(inner),
+        })
+    }
+
     fn set_elem(&mut self) -> RegexResult {
-        let first_char = self.next().ok_or(())?;
+        let first_char = self.next().ok_or_else(|| self.eof())?;
 
         if let Some('-') = self.peek() {
             let _ = self.next();
-            let second_char = self.next().ok_or(())?;
-            Ok(Regex::Range(first_char..second_char))
+            let second_char = self.next().ok_or_else(|| self.eof())?;
+            if second_char < first_char {
+                return Err(self.error(
+                    ParseErrorKind::EmptyRange,
+                    format!("range `{first_char}-{second_char}` is empty"),
+                ));
+            }
+            Ok(Regex::Range(first_char..=second_char))
         } else {
             Ok(Regex::Char(first_char))
         }
@@ -166,10 +370,8 @@ impl<'a> Parser<'a> {
 #[cfg(test)]
 mod test {
     use crate::parse::{Parser, Regex, Regex::*};
-
-    fn char_seq(char: char) -> Regex {
-        Sequence(vec![Char(char)])
-    }
+    use alloc::boxed::Box;
+    use alloc::string::ToString;
 
     fn box_char_seq(char: char) -> Box<Regex> {
         Box::new(Sequence(vec![Char(char)]))
@@ -208,10 +410,89 @@ mod test {
         let parsed = Parser::parse(regex).unwrap();
         assert_eq!(
             parsed,
-            Sequence(vec![char_seq('a'), Sequence(vec![Char('b'), Char('c')])])
+            Sequence(vec![
+                Regex::Capture {
+                    index: 1,
+                    name: None,
+                    inner: box_char_seq('a'),
+                },
+                Regex::Capture {
+                    index: 2,
+                    name: None,
+                    inner: Box::new(Sequence(vec![Char('b'), Char('c')])),
+                },
+            ])
+        )
+    }
+
+    #[test]
+    fn named_and_non_capturing_groups() {
+        assert_eq!(
+            Parser::parse("(?<foo>a)").unwrap(),
+            Sequence(vec![Regex::Capture {
+                index: 1,
+                name: Some("foo".to_string()),
+                inner: box_char_seq('a'),
+            }])
+        );
+        // `(?:...)` groups only, and leave no capture behind
+        assert_eq!(
+            Parser::parse("(?:ab)").unwrap(),
+            Sequence(vec![Sequence(vec![Char('a'), Char('b')])])
+        );
+    }
+
+    #[test]
+    fn anchors() {
+        use super::AnchorKind::*;
+        let parsed = Parser::parse("^a\\b$").unwrap();
+        assert_eq!(
+            parsed,
+            Sequence(vec![
+                Regex::Anchor(Start),
+                Char('a'),
+                Regex::Anchor(WordBoundary),
+                Regex::Anchor(End),
+            ])
         )
     }
 
+    #[test]
+    fn error_unbalanced_paren() {
+        let err = Parser::parse("a)").unwrap_err();
+        assert_eq!(err.kind, super::ParseErrorKind::UnbalancedParen);
+    }
+
+    #[test]
+    fn error_invalid_escape() {
+        let err = Parser::parse("\\q").unwrap_err();
+        assert_eq!(err.kind, super::ParseErrorKind::InvalidEscape('q'));
+    }
+
+    #[test]
+    fn error_empty_range() {
+        let err = Parser::parse("[z-a]").unwrap_err();
+        assert_eq!(err.kind, super::ParseErrorKind::EmptyRange);
+    }
+
+    #[test]
+    fn error_unclosed_set() {
+        let err = Parser::parse("[ab").unwrap_err();
+        assert_eq!(err.kind, super::ParseErrorKind::UnclosedSet);
+    }
+
+    #[test]
+    fn error_invalid_group() {
+        let err = Parser::parse("(?!a)").unwrap_err();
+        assert_eq!(err.kind, super::ParseErrorKind::InvalidGroup);
+    }
+
+    #[test]
+    fn recovery_collects_multiple_errors() {
+        let (_, errors) = Parser::parse_recovering("a)b)");
+        assert_eq!(errors.len(), 2);
+    }
+
     #[test]
     fn set() {
         let regex = "[ab]";
@@ -229,8 +510,8 @@ mod test {
         assert_eq!(
             parsed,
             Sequence(vec![Regex::Set(vec![
-                Regex::Range('a'..'z'),
-                Regex::Range('A'..'Z')
+                Regex::Range('a'..='z'),
+                Regex::Range('A'..='Z')
             ])])
         )
     }